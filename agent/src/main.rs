@@ -0,0 +1,126 @@
+//! Remote execution agent. Registers with a coordinator (if one is
+//! configured) and then listens for `Workflow` jobs dispatched directly from
+//! a desktop client, running each one the same way the client's local mode
+//! would and reporting the result back over the same connection.
+use shared::{generate_python_code, run_python_code_streaming, JobRequest, JobResponse, JobStatus};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+
+const DEFAULT_LISTEN_ADDR: &str = "0.0.0.0:7878";
+
+/// Registers this agent with a coordinator by sending a single-line
+/// `REGISTER <listen_addr>` announcement. Best-effort: a coordinator that
+/// isn't reachable doesn't stop the agent from serving jobs directly.
+fn register_with_coordinator(coordinator_addr: &str, listen_addr: &str) {
+    match TcpStream::connect(coordinator_addr) {
+        Ok(mut stream) => {
+            let _ = writeln!(stream, "REGISTER {}", listen_addr);
+        },
+        Err(e) => {
+            eprintln!("could not register with coordinator at {}: {}", coordinator_addr, e);
+        }
+    }
+}
+
+/// Run `request` to completion, writing one `Running` `JobResponse` line with
+/// any Lua script output, then one `Running` line per line of Python stdout
+/// as it's produced, followed by exactly one final
+/// `Finished`/`Failed` line carrying the full accumulated output. The
+/// dispatcher reads lines off this same connection until it sees that final
+/// line, so a caller polling in the meantime sees real partial output instead
+/// of only the end result.
+fn run_job(request: JobRequest, mut on_progress: impl FnMut(&JobResponse)) -> JobResponse {
+    let generated = match generate_python_code(&request.workflow) {
+        Ok(generated) => generated,
+        Err(e) => {
+            return JobResponse {
+                job_id: request.job_id,
+                status: JobStatus::Failed,
+                output: String::new(),
+                error: Some(e),
+            }
+        }
+    };
+
+    let job_id = request.job_id.clone();
+    if !generated.lua_output.is_empty() {
+        on_progress(&JobResponse {
+            job_id: job_id.clone(),
+            status: JobStatus::Running,
+            output: generated.lua_output.clone(),
+            error: None,
+        });
+    }
+
+    let mut streamed = String::new();
+    let result = run_python_code_streaming(&generated.python_code, |line| {
+        streamed.push_str(line);
+        streamed.push('\n');
+        on_progress(&JobResponse {
+            job_id: job_id.clone(),
+            status: JobStatus::Running,
+            output: format!("{}\n", line),
+            error: None,
+        });
+    });
+
+    match result {
+        Ok(output) => JobResponse {
+            job_id: request.job_id,
+            status: JobStatus::Finished,
+            output: format!("{}{}", generated.lua_output, output),
+            error: None,
+        },
+        Err(e) => JobResponse {
+            job_id: request.job_id,
+            status: JobStatus::Failed,
+            output: format!("{}{}", generated.lua_output, streamed),
+            error: Some(e),
+        },
+    }
+}
+
+fn handle_connection(stream: TcpStream) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+
+    let request: JobRequest = match serde_json::from_str(line.trim()) {
+        Ok(request) => request,
+        Err(e) => {
+            eprintln!("dropping malformed job request: {}", e);
+            return Ok(());
+        }
+    };
+
+    let mut writer = stream;
+    let response = run_job(request, |progress| {
+        if let Ok(line) = serde_json::to_string(progress) {
+            let _ = writeln!(writer, "{}", line);
+        }
+    });
+    writeln!(writer, "{}", serde_json::to_string(&response)?)?;
+    Ok(())
+}
+
+fn main() {
+    let listen_addr = std::env::args().nth(1).unwrap_or_else(|| DEFAULT_LISTEN_ADDR.to_string());
+
+    if let Some(coordinator_addr) = std::env::args().nth(2) {
+        register_with_coordinator(&coordinator_addr, &listen_addr);
+    }
+
+    let listener = TcpListener::bind(&listen_addr).expect("failed to bind agent listener");
+    println!("agentBlocks agent listening on {}", listen_addr);
+
+    for incoming in listener.incoming() {
+        match incoming {
+            Ok(stream) => {
+                if let Err(e) = handle_connection(stream) {
+                    eprintln!("job connection failed: {}", e);
+                }
+            },
+            Err(e) => eprintln!("failed to accept connection: {}", e),
+        }
+    }
+}
@@ -1,42 +1,61 @@
 use serde::{Deserialize, Serialize};
+use shared::{
+    execute_lua_code, generate_python_code, run_python_code_blocking, topological_order,
+    Connection, JobRecord, JobRequest, JobResponse, JobStatus, Position, Workflow, WorkflowNode,
+};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use tauri::Manager;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct WorkflowNode {
-    id: String,
-    node_type: String,
-    position: Position,
-    properties: HashMap<String, serde_json::Value>,
+#[derive(Debug, Serialize, Deserialize)]
+struct ExecutionResult {
+    success: bool,
+    output: String,
+    error: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Position {
-    x: f64,
-    y: f64,
+/// Per-node execution status, emitted to the frontend as `node-state-changed`
+/// events so the editor can highlight the block the workflow is currently on.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum NodeState {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Connection {
-    id: String,
-    source_node: String,
-    target_node: String,
+#[derive(Debug, Clone, Serialize)]
+struct NodeStateChanged {
+    node_id: String,
+    state: NodeState,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct WorkflowOutputLine {
+    stream: &'static str,
+    line: String,
 }
 
+/// A single static-analysis finding from `validate_workflow`, in the spirit
+/// of a language server diagnostic: optionally anchored to a node, with a
+/// severity ("error" or "warning") and a human-readable message.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct Workflow {
-    id: String,
-    name: String,
-    nodes: Vec<WorkflowNode>,
-    connections: Vec<Connection>,
+struct Diagnostic {
+    node_id: Option<String>,
+    severity: String,
+    message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct ExecutionResult {
-    success: bool,
-    output: String,
-    error: Option<String>,
+const KNOWN_NODE_TYPES: &[&str] = &["variable", "print", "script"];
+
+fn looks_like_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {},
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
 }
 
 // Tauri Commands
@@ -98,19 +117,132 @@ async fn update_node_properties(mut workflow: Workflow, node_id: String, propert
     }
 }
 
+/// Statically analyze a workflow without executing it, the way a language
+/// server analyzes a document on every keystroke. Flags unknown node types,
+/// duplicate variable declarations, `print` nodes referencing undeclared
+/// variables, nodes with no connections at all (only in workflows of more
+/// than one node), and cycles in the connection
+/// graph (reusing the same detection `topological_order` uses at run time).
 #[tauri::command]
-async fn execute_workflow(workflow: Workflow) -> Result<ExecutionResult, String> {
+async fn validate_workflow(workflow: Workflow) -> Result<Vec<Diagnostic>, String> {
+    let mut diagnostics = Vec::new();
+
+    let mut declarations: HashMap<&str, Vec<&str>> = HashMap::new();
+    for node in &workflow.nodes {
+        if !KNOWN_NODE_TYPES.contains(&node.node_type.as_str()) {
+            diagnostics.push(Diagnostic {
+                node_id: Some(node.id.clone()),
+                severity: "error".to_string(),
+                message: format!("unknown node type: {}", node.node_type),
+            });
+        }
+
+        if node.node_type == "variable" {
+            if let Some(name) = node.properties.get("name").and_then(|v| v.as_str()) {
+                declarations.entry(name).or_default().push(node.id.as_str());
+            }
+        }
+    }
+
+    for (name, node_ids) in &declarations {
+        if node_ids.len() > 1 {
+            diagnostics.push(Diagnostic {
+                node_id: None,
+                severity: "error".to_string(),
+                message: format!("duplicate variable name \"{}\" declared by nodes: {}", name, node_ids.join(", ")),
+            });
+        }
+    }
+
+    for node in &workflow.nodes {
+        if node.node_type == "print" {
+            if let Some(message) = node.properties.get("message").and_then(|v| v.as_str()) {
+                if looks_like_identifier(message) && !declarations.contains_key(message) {
+                    diagnostics.push(Diagnostic {
+                        node_id: Some(node.id.clone()),
+                        severity: "error".to_string(),
+                        message: format!("use of undefined variable \"{}\"", message),
+                    });
+                }
+            }
+        }
+    }
+
+    let mut connected: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    for conn in &workflow.connections {
+        connected.insert(conn.source_node.as_str());
+        connected.insert(conn.target_node.as_str());
+    }
+    if workflow.nodes.len() > 1 {
+        for node in &workflow.nodes {
+            if !connected.contains(node.id.as_str()) {
+                diagnostics.push(Diagnostic {
+                    node_id: Some(node.id.clone()),
+                    severity: "warning".to_string(),
+                    message: "orphaned node: no incoming or outgoing connections".to_string(),
+                });
+            }
+        }
+    }
+
+    if let Err(e) = topological_order(&workflow) {
+        diagnostics.push(Diagnostic {
+            node_id: None,
+            severity: "error".to_string(),
+            message: e,
+        });
+    }
+
+    Ok(diagnostics)
+}
+
+fn emit_node_state(app_handle: &tauri::AppHandle, node_id: &str, state: NodeState) {
+    let _ = app_handle.emit_all("node-state-changed", NodeStateChanged {
+        node_id: node_id.to_string(),
+        state,
+    });
+}
+
+/// Appends incrementally-produced output to a dispatched job's registry
+/// entry as it's produced, so `poll_job` can report partial output while a
+/// job is still `Running` instead of only once it finishes.
+#[derive(Clone)]
+struct JobSink {
+    jobs: JobRegistry,
+    job_id: String,
+}
+
+impl JobSink {
+    fn append_output(&self, chunk: &str) {
+        if let Some(record) = self.jobs.lock().unwrap().get_mut(&self.job_id) {
+            record.output.push_str(chunk);
+        }
+    }
+}
+
+/// Core workflow execution shared by the `execute_workflow` command and
+/// `run_local_job`. `job_sink`, when present, is fed output as it's produced
+/// (Lua `print` output, then Python stdout/stderr lines) so a dispatched
+/// job's registry entry reflects partial output while still `Running`.
+async fn run_workflow(app_handle: tauri::AppHandle, workflow: Workflow, job_sink: Option<JobSink>) -> ExecutionResult {
     let mut python_code = String::from("# Generated Python Code\n");
+    let mut lua_output = String::new();
     let mut variables = HashMap::new();
 
-    // Sort nodes by execution order (simple left-to-right, top-to-bottom)
-    let mut sorted_nodes = workflow.nodes.clone();
-    sorted_nodes.sort_by(|a, b| {
-        a.position.y.partial_cmp(&b.position.y).unwrap_or(std::cmp::Ordering::Equal)
-            .then(a.position.x.partial_cmp(&b.position.x).unwrap_or(std::cmp::Ordering::Equal))
-    });
+    let sorted_nodes = match topological_order(&workflow) {
+        Ok(nodes) => nodes,
+        Err(e) => {
+            return ExecutionResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            }
+        }
+    };
+
+    for node in &sorted_nodes {
+        emit_node_state(&app_handle, &node.id, NodeState::Running);
 
-    for node in sorted_nodes {
         match node.node_type.as_str() {
             "variable" => {
                 let name = node.properties.get("name")
@@ -119,69 +251,411 @@ async fn execute_workflow(workflow: Workflow) -> Result<ExecutionResult, String>
                 let value = node.properties.get("value")
                     .and_then(|v| v.as_str())
                     .unwrap_or("hello world");
-                
+
                 python_code.push_str(&format!("{} = \"{}\"\n", name, value));
                 variables.insert(name.to_string(), value.to_string());
+                emit_node_state(&app_handle, &node.id, NodeState::Succeeded);
             },
             "print" => {
                 let message = node.properties.get("message")
                     .and_then(|v| v.as_str())
                     .unwrap_or("myVariable");
-                
+
                 // Check if message is a variable name
                 if variables.contains_key(message) {
                     python_code.push_str(&format!("print({})\n", message));
                 } else {
                     python_code.push_str(&format!("print(\"{}\")\n", message));
                 }
+                emit_node_state(&app_handle, &node.id, NodeState::Succeeded);
+            },
+            "script" => {
+                let code = node.properties.get("code")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("");
+
+                match execute_lua_code(code, &mut variables) {
+                    Ok(output) => {
+                        lua_output.push_str(&output);
+                        if let Some(sink) = &job_sink {
+                            sink.append_output(&output);
+                        }
+                        emit_node_state(&app_handle, &node.id, NodeState::Succeeded);
+                    },
+                    Err(e) => {
+                        emit_node_state(&app_handle, &node.id, NodeState::Failed);
+                        return ExecutionResult {
+                            success: false,
+                            output: lua_output,
+                            error: Some(e),
+                        }
+                    }
+                }
             },
             _ => {
                 python_code.push_str(&format!("# Unknown node type: {}\n", node.node_type));
+                emit_node_state(&app_handle, &node.id, NodeState::Succeeded);
             }
         }
     }
 
-    // Execute Python code
-    match execute_python_code(&python_code).await {
-        Ok(output) => Ok(ExecutionResult {
+    // Execute Python code, streaming stdout/stderr to the frontend (and to
+    // job_sink, if this run was dispatched as a job) as it arrives
+    match execute_python_code(&app_handle, &python_code, job_sink.clone()).await {
+        Ok(output) => ExecutionResult {
             success: true,
-            output,
+            output: format!("{}{}", lua_output, output),
             error: None,
-        }),
-        Err(e) => Ok(ExecutionResult {
-            success: false,
-            output: python_code,
-            error: Some(e),
-        }),
+        },
+        Err(e) => {
+            for node in &sorted_nodes {
+                if node.node_type != "script" {
+                    emit_node_state(&app_handle, &node.id, NodeState::Failed);
+                }
+            }
+            ExecutionResult {
+                success: false,
+                output: format!("{}{}", lua_output, python_code),
+                error: Some(e),
+            }
+        },
     }
 }
 
-async fn execute_python_code(code: &str) -> Result<String, String> {
-    use std::process::Command;
+#[tauri::command]
+async fn execute_workflow(app_handle: tauri::AppHandle, workflow: Workflow) -> Result<ExecutionResult, String> {
+    Ok(run_workflow(app_handle, workflow, None).await)
+}
+
+/// Read `reader` line by line, forwarding each line to the frontend as a
+/// `workflow-output` event as soon as it arrives (and, if this run is backed
+/// by a dispatched job, to `job_sink`), while also accumulating the full
+/// text for the final `ExecutionResult`.
+async fn stream_lines_to_frontend<R: tokio::io::AsyncRead + Unpin>(
+    app_handle: tauri::AppHandle,
+    reader: R,
+    stream: &'static str,
+    job_sink: Option<JobSink>,
+) -> String {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let mut lines = BufReader::new(reader).lines();
+    let mut collected = String::new();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        let _ = app_handle.emit_all("workflow-output", WorkflowOutputLine {
+            stream,
+            line: line.clone(),
+        });
+        if let Some(sink) = &job_sink {
+            sink.append_output(&format!("{}\n", line));
+        }
+        collected.push_str(&line);
+        collected.push('\n');
+    }
+
+    collected
+}
+
+async fn execute_python_code(app_handle: &tauri::AppHandle, code: &str, job_sink: Option<JobSink>) -> Result<String, String> {
+    use tokio::process::Command;
+    use std::process::Stdio;
 
     // Create a temporary Python file
     let temp_file = format!("/tmp/agentblocks_{}.py", Uuid::new_v4());
-    
+
     // Write code to file
     std::fs::write(&temp_file, code)
         .map_err(|e| format!("Failed to write Python file: {}", e))?;
 
-    // Execute Python script
-    let output = Command::new("python3")
+    // Spawn the interpreter with piped stdout/stderr so output can be streamed
+    // to the webview as it's produced, instead of only once the process exits.
+    // -u disables stdout/stderr buffering: CPython fully block-buffers when
+    // its output isn't a tty, which would otherwise deliver lines in one
+    // final burst at exit instead of as they're printed, defeating the
+    // streaming this function exists for.
+    let mut child = Command::new("python3")
+        .arg("-u")
         .arg(&temp_file)
-        .output()
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
         .map_err(|e| format!("Failed to execute Python: {}", e))?;
 
+    let stdout = child.stdout.take().expect("child stdout was piped");
+    let stderr = child.stderr.take().expect("child stderr was piped");
+
+    let stdout_task = tokio::spawn(stream_lines_to_frontend(app_handle.clone(), stdout, "stdout", job_sink.clone()));
+    let stderr_task = tokio::spawn(stream_lines_to_frontend(app_handle.clone(), stderr, "stderr", job_sink));
+
+    let status = child.wait().await
+        .map_err(|e| format!("Failed to wait on Python process: {}", e))?;
+    let stdout_output = stdout_task.await.unwrap_or_default();
+    let stderr_output = stderr_task.await.unwrap_or_default();
+
     // Clean up temp file
     let _ = std::fs::remove_file(&temp_file);
 
-    if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    if status.success() {
+        Ok(stdout_output)
     } else {
-        Err(String::from_utf8_lossy(&output.stderr).to_string())
+        Err(stderr_output)
+    }
+}
+
+/// Shared map of in-flight and completed jobs, keyed by job id. Managed as
+/// Tauri state so `dispatch_workflow` and `poll_job` see the same registry.
+type JobRegistry = Arc<Mutex<HashMap<String, JobRecord>>>;
+
+fn set_job(jobs: &JobRegistry, job_id: &str, record: JobRecord) {
+    jobs.lock().unwrap().insert(job_id.to_string(), record);
+}
+
+/// Run a dispatched job the same way `execute_workflow` does, in-process,
+/// passing a `JobSink` through so the registry entry accumulates real output
+/// as the workflow runs instead of only once it finishes.
+async fn run_local_job(app_handle: tauri::AppHandle, jobs: JobRegistry, job_id: String, workflow: Workflow) {
+    set_job(&jobs, &job_id, JobRecord { status: JobStatus::Running, output: String::new(), error: None });
+
+    let sink = JobSink { jobs: jobs.clone(), job_id: job_id.clone() };
+    let result = run_workflow(app_handle, workflow, Some(sink)).await;
+
+    set_job(&jobs, &job_id, JobRecord {
+        status: if result.success { JobStatus::Finished } else { JobStatus::Failed },
+        output: result.output,
+        error: result.error,
+    });
+}
+
+/// Hand a job off to a remote agent over a newline-delimited JSON TCP
+/// protocol: send one `JobRequest` line, then read `JobResponse` lines back
+/// until a terminal (`Finished`/`Failed`) one arrives. The agent writes a
+/// `Running` line per chunk of output it produces, so `job_sink` is kept
+/// updated with partial output the same way `run_local_job` is, instead of
+/// only learning the result once the job finishes.
+async fn run_remote_job(jobs: JobRegistry, job_id: String, workflow: Workflow, agent_address: String) {
+    set_job(&jobs, &job_id, JobRecord { status: JobStatus::Running, output: String::new(), error: None });
+
+    let sink = JobSink { jobs: jobs.clone(), job_id: job_id.clone() };
+    let outcome = run_remote_job_io(&job_id, workflow, &agent_address, &sink).await;
+
+    let record = match outcome {
+        Ok(response) => JobRecord {
+            status: response.status,
+            output: response.output,
+            error: response.error,
+        },
+        Err(e) => JobRecord { status: JobStatus::Failed, output: String::new(), error: Some(e) },
+    };
+
+    set_job(&jobs, &job_id, record);
+}
+
+async fn run_remote_job_io(job_id: &str, workflow: Workflow, agent_address: &str, job_sink: &JobSink) -> Result<JobResponse, String> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpStream;
+
+    let request = JobRequest { job_id: job_id.to_string(), workflow };
+    let request_line = serde_json::to_string(&request)
+        .map_err(|e| format!("Failed to serialize job request: {}", e))?;
+
+    let mut stream = TcpStream::connect(agent_address).await
+        .map_err(|e| format!("Failed to connect to agent at {}: {}", agent_address, e))?;
+    stream.write_all(request_line.as_bytes()).await
+        .map_err(|e| format!("Failed to send job to agent: {}", e))?;
+    stream.write_all(b"\n").await
+        .map_err(|e| format!("Failed to send job to agent: {}", e))?;
+
+    let mut lines = BufReader::new(stream).lines();
+    loop {
+        let line = lines.next_line().await
+            .map_err(|e| format!("Failed to read agent response: {}", e))?
+            .ok_or_else(|| "agent closed the connection before reporting a result".to_string())?;
+
+        let response: JobResponse = serde_json::from_str(line.trim())
+            .map_err(|e| format!("Failed to parse agent response: {}", e))?;
+
+        if response.status == JobStatus::Running {
+            job_sink.append_output(&response.output);
+        } else {
+            return Ok(response);
+        }
     }
 }
 
+/// Dispatch a workflow for execution and return immediately with a job id;
+/// use `poll_job` to observe progress. `mode` is `"local"` (default, runs
+/// in-process exactly as `execute_workflow` would) or `"remote"`, which
+/// requires `agent_address` and hands the workflow off to an agent there.
+#[tauri::command]
+async fn dispatch_workflow(
+    app_handle: tauri::AppHandle,
+    jobs: tauri::State<'_, JobRegistry>,
+    workflow: Workflow,
+    mode: String,
+    agent_address: Option<String>,
+) -> Result<String, String> {
+    let job_id = Uuid::new_v4().to_string();
+    let jobs_handle = jobs.inner().clone();
+    set_job(&jobs_handle, &job_id, JobRecord::queued());
+
+    match mode.as_str() {
+        "remote" => {
+            let agent_address = agent_address
+                .ok_or_else(|| "agent_address is required when mode is \"remote\"".to_string())?;
+            tokio::spawn(run_remote_job(jobs_handle, job_id.clone(), workflow, agent_address));
+        },
+        _ => {
+            tokio::spawn(run_local_job(app_handle, jobs_handle, job_id.clone(), workflow));
+        }
+    }
+
+    Ok(job_id)
+}
+
+#[tauri::command]
+async fn poll_job(jobs: tauri::State<'_, JobRegistry>, job_id: String) -> Result<JobRecord, String> {
+    jobs.lock().unwrap().get(&job_id).cloned()
+        .ok_or_else(|| format!("unknown job id: {}", job_id))
+}
+
+/// One workflow entry in a benchmark workload file: the workflow itself,
+/// how many timed iterations to run, and how many untimed warmup iterations
+/// to run first so interpreter/filesystem caches are warm before sampling.
+#[derive(Debug, Clone, Deserialize)]
+struct BenchmarkTarget {
+    workflow: Workflow,
+    repeat: usize,
+    #[serde(default)]
+    warmup: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct StageTimings {
+    min_ms: f64,
+    max_ms: f64,
+    mean_ms: f64,
+    median_ms: f64,
+}
+
+impl StageTimings {
+    fn from_samples(mut samples_ms: Vec<f64>) -> StageTimings {
+        samples_ms.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let len = samples_ms.len();
+        let median_ms = if len % 2 == 0 {
+            (samples_ms[len / 2 - 1] + samples_ms[len / 2]) / 2.0
+        } else {
+            samples_ms[len / 2]
+        };
+
+        StageTimings {
+            min_ms: samples_ms.first().copied().unwrap_or(0.0),
+            max_ms: samples_ms.last().copied().unwrap_or(0.0),
+            mean_ms: samples_ms.iter().sum::<f64>() / len as f64,
+            median_ms,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct WorkflowBenchmarkReport {
+    workflow_name: String,
+    repeat: usize,
+    codegen: StageTimings,
+    script_execution: StageTimings,
+    python_execution: StageTimings,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct EnvironmentInfo {
+    os: String,
+    python3_version: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct BenchmarkReport {
+    environment: EnvironmentInfo,
+    workflows: Vec<WorkflowBenchmarkReport>,
+}
+
+fn python3_version() -> String {
+    use std::process::Command;
+
+    Command::new("python3")
+        .arg("--version")
+        .output()
+        .map(|o| {
+            let text = if !o.stdout.is_empty() { o.stdout } else { o.stderr };
+            String::from_utf8_lossy(&text).trim().to_string()
+        })
+        .unwrap_or_else(|e| format!("unavailable: {}", e))
+}
+
+/// Run every workflow in a workload file `repeat` times (after `warmup`
+/// untimed iterations), timing code generation, script node execution, and
+/// Python execution separately, and return a min/max/mean/median report per
+/// workflow plus the captured environment. script_execution is broken out
+/// from codegen because generate_python_code runs script nodes' Lua inline
+/// as part of generating code — without this split a slow Lua script would
+/// show up as a codegen regression, and codegen numbers wouldn't be
+/// comparable between workflows that do and don't use script nodes.
+/// Intended for regression tracking across crate
+/// versions as node counts grow.
+#[tauri::command]
+async fn run_benchmark(workload_path: String) -> Result<BenchmarkReport, String> {
+    let contents = std::fs::read_to_string(&workload_path)
+        .map_err(|e| format!("Failed to read workload file: {}", e))?;
+    let targets: Vec<BenchmarkTarget> = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse workload file: {}", e))?;
+
+    let environment = EnvironmentInfo {
+        os: std::env::consts::OS.to_string(),
+        python3_version: python3_version(),
+    };
+
+    let mut workflows = Vec::with_capacity(targets.len());
+    for target in targets {
+        if target.repeat == 0 {
+            return Err(format!("workload entry \"{}\" has repeat: 0, must be at least 1", target.workflow.name));
+        }
+
+        for _ in 0..target.warmup {
+            if let Ok(generated) = generate_python_code(&target.workflow) {
+                let _ = run_python_code_blocking(&generated.python_code);
+            }
+        }
+
+        let mut codegen_samples = Vec::with_capacity(target.repeat);
+        let mut script_samples = Vec::with_capacity(target.repeat);
+        let mut execution_samples = Vec::with_capacity(target.repeat);
+
+        for _ in 0..target.repeat {
+            let codegen_start = std::time::Instant::now();
+            let generated = generate_python_code(&target.workflow)?;
+            let codegen_elapsed = codegen_start.elapsed();
+            // generate_python_code runs script nodes' Lua inline (later nodes
+            // can depend on variables they set), so the time it spent there
+            // is bucketed under script_execution rather than codegen.
+            codegen_samples.push((codegen_elapsed - generated.lua_elapsed).as_secs_f64() * 1000.0);
+            script_samples.push(generated.lua_elapsed.as_secs_f64() * 1000.0);
+
+            let execution_start = std::time::Instant::now();
+            run_python_code_blocking(&generated.python_code)?;
+            execution_samples.push(execution_start.elapsed().as_secs_f64() * 1000.0);
+        }
+
+        workflows.push(WorkflowBenchmarkReport {
+            workflow_name: target.workflow.name.clone(),
+            repeat: target.repeat,
+            codegen: StageTimings::from_samples(codegen_samples),
+            script_execution: StageTimings::from_samples(script_samples),
+            python_execution: StageTimings::from_samples(execution_samples),
+        });
+    }
+
+    Ok(BenchmarkReport { environment, workflows })
+}
+
 #[tauri::command]
 async fn save_workflow(workflow: Workflow, path: String) -> Result<(), String> {
     let json = serde_json::to_string_pretty(&workflow)
@@ -220,14 +694,93 @@ async fn load_workflow_json(json_content: String) -> Result<Workflow, String> {
     Ok(workflow)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, node_type: &str, properties: HashMap<String, serde_json::Value>) -> WorkflowNode {
+        WorkflowNode {
+            id: id.to_string(),
+            node_type: node_type.to_string(),
+            position: Position { x: 0.0, y: 0.0 },
+            properties,
+        }
+    }
+
+    fn workflow(nodes: Vec<WorkflowNode>, connections: Vec<Connection>) -> Workflow {
+        Workflow { id: "wf".to_string(), name: "test".to_string(), nodes, connections }
+    }
+
+    fn severities(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics.iter().map(|d| d.severity.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn validate_workflow_flags_unknown_node_type() {
+        let wf = workflow(vec![node("a", "frobnicate", HashMap::new())], vec![]);
+        let diagnostics = validate_workflow(wf).await.unwrap();
+        assert!(diagnostics.iter().any(|d| d.severity == "error" && d.message.contains("unknown node type")));
+    }
+
+    #[tokio::test]
+    async fn validate_workflow_flags_duplicate_variable_declarations() {
+        let mut props_a = HashMap::new();
+        props_a.insert("name".to_string(), serde_json::Value::String("x".to_string()));
+        let mut props_b = HashMap::new();
+        props_b.insert("name".to_string(), serde_json::Value::String("x".to_string()));
+
+        let wf = workflow(
+            vec![node("a", "variable", props_a), node("b", "variable", props_b)],
+            vec![],
+        );
+        let diagnostics = validate_workflow(wf).await.unwrap();
+        assert!(diagnostics.iter().any(|d| d.severity == "error" && d.message.contains("duplicate variable name")));
+    }
+
+    #[tokio::test]
+    async fn validate_workflow_flags_undeclared_variable_reference() {
+        let mut props = HashMap::new();
+        props.insert("message".to_string(), serde_json::Value::String("missing".to_string()));
+
+        let wf = workflow(vec![node("a", "print", props)], vec![]);
+        let diagnostics = validate_workflow(wf).await.unwrap();
+        assert!(diagnostics.iter().any(|d| d.severity == "error" && d.message.contains("undefined variable")));
+    }
+
+    #[tokio::test]
+    async fn validate_workflow_flags_orphaned_nodes() {
+        let wf = workflow(
+            vec![node("a", "print", HashMap::new()), node("b", "print", HashMap::new())],
+            vec![],
+        );
+        let diagnostics = validate_workflow(wf).await.unwrap();
+        assert_eq!(severities(&diagnostics), vec!["warning", "warning"]);
+    }
+
+    #[tokio::test]
+    async fn validate_workflow_accepts_a_clean_workflow() {
+        let mut props = HashMap::new();
+        props.insert("name".to_string(), serde_json::Value::String("x".to_string()));
+
+        let wf = workflow(vec![node("a", "variable", props)], vec![]);
+        let diagnostics = validate_workflow(wf).await.unwrap();
+        assert!(diagnostics.is_empty(), "expected no diagnostics, got: {diagnostics:?}");
+    }
+}
+
 fn main() {
     tauri::Builder::default()
+        .manage(JobRegistry::default())
         .invoke_handler(tauri::generate_handler![
             create_workflow,
             add_node,
             update_node_position,
             update_node_properties,
             execute_workflow,
+            validate_workflow,
+            dispatch_workflow,
+            poll_job,
+            run_benchmark,
             save_workflow,
             load_workflow,
             save_workflow_json,
@@ -0,0 +1,391 @@
+//! Types and codegen logic shared between the desktop client and the remote
+//! execution agent, so a workflow dispatched to either one is interpreted
+//! identically.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowNode {
+    pub id: String,
+    pub node_type: String,
+    pub position: Position,
+    pub properties: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Connection {
+    pub id: String,
+    pub source_node: String,
+    pub target_node: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workflow {
+    pub id: String,
+    pub name: String,
+    pub nodes: Vec<WorkflowNode>,
+    pub connections: Vec<Connection>,
+}
+
+/// The lifecycle of a workflow dispatched to a (local or remote) runner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Finished,
+    Failed,
+}
+
+/// A job as sent over the wire from the desktop client to an agent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRequest {
+    pub job_id: String,
+    pub workflow: Workflow,
+}
+
+/// A job's current state as tracked locally by `poll_job`. `output`
+/// accumulates whatever has run so far, so a caller can observe partial
+/// output while the job is still `Running`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub status: JobStatus,
+    pub output: String,
+    pub error: Option<String>,
+}
+
+impl JobRecord {
+    pub fn queued() -> JobRecord {
+        JobRecord { status: JobStatus::Queued, output: String::new(), error: None }
+    }
+}
+
+/// A job's result as reported back over the wire from an agent to whoever
+/// dispatched it. Carries `job_id` explicitly since it travels independently
+/// of any local map key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobResponse {
+    pub job_id: String,
+    pub status: JobStatus,
+    pub output: String,
+    pub error: Option<String>,
+}
+
+/// Order nodes for execution using Kahn's algorithm over `workflow.connections`,
+/// so data actually flows from producers to consumers regardless of where the
+/// user dropped the blocks on the canvas. Nodes with no dependencies between
+/// them are ordered by position (top-to-bottom, then left-to-right) so layout
+/// still acts as a tiebreaker. Returns an error listing the unresolved node
+/// ids if the connection graph contains a cycle.
+pub fn topological_order(workflow: &Workflow) -> Result<Vec<WorkflowNode>, String> {
+    let mut in_degree: HashMap<&str, usize> = workflow.nodes.iter().map(|n| (n.id.as_str(), 0)).collect();
+    let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for conn in &workflow.connections {
+        if let Some(degree) = in_degree.get_mut(conn.target_node.as_str()) {
+            *degree += 1;
+        }
+        successors.entry(conn.source_node.as_str()).or_default().push(conn.target_node.as_str());
+    }
+
+    let position_order = |a: &WorkflowNode, b: &WorkflowNode| {
+        a.position.y.partial_cmp(&b.position.y).unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.position.x.partial_cmp(&b.position.x).unwrap_or(std::cmp::Ordering::Equal))
+    };
+
+    let mut ready: Vec<&WorkflowNode> = workflow.nodes.iter()
+        .filter(|n| in_degree.get(n.id.as_str()).copied().unwrap_or(0) == 0)
+        .collect();
+    ready.sort_by(|a, b| position_order(b, a));
+
+    let nodes_by_id: HashMap<&str, &WorkflowNode> = workflow.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    let mut ordered = Vec::with_capacity(workflow.nodes.len());
+
+    while let Some(node) = ready.pop() {
+        ordered.push(node.clone());
+
+        let mut newly_ready = Vec::new();
+        if let Some(targets) = successors.get(node.id.as_str()) {
+            for target_id in targets {
+                if let Some(degree) = in_degree.get_mut(target_id) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        if let Some(target_node) = nodes_by_id.get(target_id) {
+                            newly_ready.push(*target_node);
+                        }
+                    }
+                }
+            }
+        }
+
+        ready.extend(newly_ready);
+        ready.sort_by(|a, b| position_order(b, a));
+    }
+
+    if ordered.len() < workflow.nodes.len() {
+        let resolved: std::collections::HashSet<&str> = ordered.iter().map(|n| n.id.as_str()).collect();
+        let unresolved: Vec<&str> = workflow.nodes.iter()
+            .map(|n| n.id.as_str())
+            .filter(|id| !resolved.contains(id))
+            .collect();
+        return Err(format!("cycle detected involving nodes: {}", unresolved.join(", ")));
+    }
+
+    Ok(ordered)
+}
+
+/// Run a `script` node's Lua source in-process via rlua. Every accumulated
+/// workflow variable is exposed as a Lua global before the script runs, and
+/// every string-valued global is read back into `variables` afterwards so
+/// downstream nodes see the result. Globals of other types (numbers,
+/// booleans, tables, functions) are not read back, so a variable the script
+/// reassigns to a non-string value keeps its previous value in `variables`.
+/// `print` is overridden to append to a
+/// captured output buffer instead of writing to stdout.
+pub fn execute_lua_code(code: &str, variables: &mut HashMap<String, String>) -> Result<String, String> {
+    use rlua::{Lua, Value};
+    use std::sync::{Arc, Mutex};
+
+    let lua = Lua::new();
+    let output = Arc::new(Mutex::new(String::new()));
+
+    lua.context(|ctx| -> rlua::Result<()> {
+        let globals = ctx.globals();
+
+        for (name, value) in variables.iter() {
+            globals.set(name.as_str(), value.as_str())?;
+        }
+
+        let print_output = Arc::clone(&output);
+        let print_fn = ctx.create_function(move |_, args: rlua::Variadic<String>| {
+            let mut buf = print_output.lock().unwrap();
+            buf.push_str(&args.join("\t"));
+            buf.push('\n');
+            Ok(())
+        })?;
+        globals.set("print", print_fn)?;
+
+        ctx.load(code).exec()?;
+
+        for pair in globals.pairs::<String, Value>() {
+            let (name, value) = pair?;
+            if let Value::String(s) = value {
+                variables.insert(name, s.to_str().unwrap_or("").to_string());
+            }
+        }
+
+        Ok(())
+    }).map_err(|e: rlua::Error| format!("Lua error: {}", e))?;
+
+    // `print_fn` keeps its own clone of `output` alive for as long as `lua`
+    // does, so the Arc's strong count is never 1 here — read the buffer out
+    // instead of trying to reclaim sole ownership of it.
+    let captured = output.lock().unwrap().clone();
+    Ok(captured)
+}
+
+/// The result of [`generate_python_code`]: the Python source itself, plus
+/// whatever `script` nodes printed along the way (Lua runs as part of
+/// codegen, since later nodes can depend on variables it sets — see
+/// `lua_elapsed` below) and how long that Lua execution took in total, so a
+/// caller that also wants to time pure codegen can subtract it back out.
+#[derive(Debug, Clone, Default)]
+pub struct GeneratedCode {
+    pub python_code: String,
+    pub lua_output: String,
+    pub lua_elapsed: std::time::Duration,
+}
+
+/// Generate the Python source for a workflow without running it (beyond
+/// `script` nodes, which run inline because downstream nodes can reference
+/// variables they set). Shared by the desktop client's benchmark harness and
+/// the remote agent, so both interpret a workflow identically. The desktop
+/// client's local execution path does not call it; it builds the same code
+/// node by node in its own loop.
+pub fn generate_python_code(workflow: &Workflow) -> Result<GeneratedCode, String> {
+    let mut python_code = String::from("# Generated Python Code\n");
+    let mut lua_output = String::new();
+    let mut lua_elapsed = std::time::Duration::default();
+    let mut variables = HashMap::new();
+
+    for node in topological_order(workflow)? {
+        match node.node_type.as_str() {
+            "variable" => {
+                let name = node.properties.get("name").and_then(|v| v.as_str()).unwrap_or("myVariable");
+                let value = node.properties.get("value").and_then(|v| v.as_str()).unwrap_or("hello world");
+                python_code.push_str(&format!("{} = \"{}\"\n", name, value));
+                variables.insert(name.to_string(), value.to_string());
+            },
+            "print" => {
+                let message = node.properties.get("message").and_then(|v| v.as_str()).unwrap_or("myVariable");
+                if variables.contains_key(message) {
+                    python_code.push_str(&format!("print({})\n", message));
+                } else {
+                    python_code.push_str(&format!("print(\"{}\")\n", message));
+                }
+            },
+            "script" => {
+                let code = node.properties.get("code").and_then(|v| v.as_str()).unwrap_or("");
+                let started = std::time::Instant::now();
+                let output = execute_lua_code(code, &mut variables)?;
+                lua_elapsed += started.elapsed();
+                lua_output.push_str(&output);
+            },
+            _ => {
+                python_code.push_str(&format!("# Unknown node type: {}\n", node.node_type));
+            }
+        }
+    }
+
+    Ok(GeneratedCode { python_code, lua_output, lua_elapsed })
+}
+
+/// Run generated Python code to completion, blocking the current thread.
+/// Used by contexts with no live frontend to stream to: the benchmark
+/// harness, which only cares about the final timed result.
+pub fn run_python_code_blocking(code: &str) -> Result<String, String> {
+    use std::process::Command;
+
+    let temp_file = format!("/tmp/agentblocks_{}.py", Uuid::new_v4());
+    std::fs::write(&temp_file, code)
+        .map_err(|e| format!("Failed to write Python file: {}", e))?;
+
+    let output = Command::new("python3")
+        .arg(&temp_file)
+        .output()
+        .map_err(|e| format!("Failed to execute Python: {}", e))?;
+    let _ = std::fs::remove_file(&temp_file);
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+/// Run generated Python code, invoking `on_line` with each line of stdout as
+/// soon as it's produced. Used by the agent, which relays these lines to its
+/// dispatcher as they arrive instead of only once the process exits.
+pub fn run_python_code_streaming(code: &str, mut on_line: impl FnMut(&str)) -> Result<String, String> {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
+
+    let temp_file = format!("/tmp/agentblocks_{}.py", Uuid::new_v4());
+    std::fs::write(&temp_file, code)
+        .map_err(|e| format!("Failed to write Python file: {}", e))?;
+
+    // -u disables stdout buffering: CPython fully block-buffers when its
+    // output isn't a tty, which would deliver `on_line` one final burst at
+    // exit instead of as each line is printed.
+    let mut child = Command::new("python3")
+        .arg("-u")
+        .arg(&temp_file)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to execute Python: {}", e))?;
+
+    let stdout = child.stdout.take().expect("child stdout was piped");
+    let mut collected = String::new();
+    for line in BufReader::new(stdout).lines() {
+        let line = line.map_err(|e| format!("Failed to read Python stdout: {}", e))?;
+        on_line(&line);
+        collected.push_str(&line);
+        collected.push('\n');
+    }
+
+    let status = child.wait().map_err(|e| format!("Failed to wait on Python process: {}", e))?;
+    let _ = std::fs::remove_file(&temp_file);
+
+    if status.success() {
+        Ok(collected)
+    } else {
+        let mut stderr_output = String::new();
+        if let Some(mut stderr) = child.stderr.take() {
+            use std::io::Read;
+            let _ = stderr.read_to_string(&mut stderr_output);
+        }
+        Err(stderr_output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, node_type: &str, x: f64, y: f64, properties: HashMap<String, serde_json::Value>) -> WorkflowNode {
+        WorkflowNode {
+            id: id.to_string(),
+            node_type: node_type.to_string(),
+            position: Position { x, y },
+            properties,
+        }
+    }
+
+    fn connection(id: &str, source: &str, target: &str) -> Connection {
+        Connection { id: id.to_string(), source_node: source.to_string(), target_node: target.to_string() }
+    }
+
+    fn workflow(nodes: Vec<WorkflowNode>, connections: Vec<Connection>) -> Workflow {
+        Workflow { id: "wf".to_string(), name: "test".to_string(), nodes, connections }
+    }
+
+    #[test]
+    fn topological_order_follows_connections_not_declaration_order() {
+        let wf = workflow(
+            vec![
+                node("b", "print", 0.0, 0.0, HashMap::new()),
+                node("a", "variable", 0.0, 0.0, HashMap::new()),
+            ],
+            vec![connection("c1", "a", "b")],
+        );
+
+        let ordered = topological_order(&wf).unwrap();
+        assert_eq!(ordered.iter().map(|n| n.id.as_str()).collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn topological_order_breaks_ties_by_position() {
+        let wf = workflow(
+            vec![
+                node("bottom", "print", 0.0, 10.0, HashMap::new()),
+                node("top", "print", 0.0, 0.0, HashMap::new()),
+            ],
+            vec![],
+        );
+
+        let ordered = topological_order(&wf).unwrap();
+        assert_eq!(ordered.iter().map(|n| n.id.as_str()).collect::<Vec<_>>(), vec!["top", "bottom"]);
+    }
+
+    #[test]
+    fn topological_order_rejects_cycles() {
+        let wf = workflow(
+            vec![
+                node("a", "print", 0.0, 0.0, HashMap::new()),
+                node("b", "print", 0.0, 0.0, HashMap::new()),
+            ],
+            vec![connection("c1", "a", "b"), connection("c2", "b", "a")],
+        );
+
+        let err = topological_order(&wf).unwrap_err();
+        assert!(err.contains('a') && err.contains('b'), "error should name the unresolved nodes: {err}");
+    }
+
+    #[test]
+    fn generate_python_code_captures_script_node_print_output() {
+        let mut script_props = HashMap::new();
+        script_props.insert("code".to_string(), serde_json::Value::String("print('hello from lua')".to_string()));
+
+        let wf = workflow(vec![node("s", "script", 0.0, 0.0, script_props)], vec![]);
+
+        let generated = generate_python_code(&wf).unwrap();
+        assert_eq!(generated.lua_output, "hello from lua\n");
+    }
+}